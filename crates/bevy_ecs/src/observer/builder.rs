@@ -97,6 +97,30 @@ impl<'w, E: EcsEvent> ObserverBuilder<'w, E> {
         self
     }
 
+    /// Sets the [`Traversal`] used to find the next target this observer's event should
+    /// bubble to once the current hop's dispatch completes.
+    pub fn traversal<T: Traversal>(&mut self) -> &mut Self {
+        let id = self
+            .commands
+            .components()
+            .get_id(TypeId::of::<T>())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Cannot observe event before it is registered: {}",
+                    std::any::type_name::<T>(),
+                )
+            });
+        self.descriptor.traversal = Some((id, traverse_erased::<T>));
+        self
+    }
+
+    /// Sets this observer's priority: lower values run first among observers matched
+    /// at the same hop. Defaults to `0`.
+    pub fn priority(&mut self, priority: i32) -> &mut Self {
+        self.descriptor.priority = priority;
+        self
+    }
+
     /// Spawns the resulting observer into the world.
     pub fn run<B: Bundle, M>(&mut self, callback: impl IntoObserverSystem<E, B, M>) -> Entity {
         B::get_component_ids(self.commands.components(), &mut |id| {
@@ -181,12 +205,84 @@ impl<'w, E: EcsEvent> EventBuilder<'w, E> {
     }
 }
 
+/// Type used to construct and emit a [`Bumped`] event, built by [`Commands::emit_with`].
+///
+/// Unlike [`EventBuilder`], `build` isn't run until [`EmitBumpedEvent::dispatch`]
+/// already has the broadcast's arena in hand, so the event's data is allocated
+/// straight into it rather than needing an already-owned value up front.
+pub struct BumpedEventBuilder<'w, T: ?Sized> {
+    event: Option<ComponentId>,
+    commands: Commands<'w, 'w>,
+    targets: Vec<Entity>,
+    components: Vec<ComponentId>,
+    build: Option<Box<dyn for<'a> FnOnce(&'a bumpalo::Bump) -> &'a T + Send + Sync>>,
+}
+
+impl<'w, T: ?Sized + Send + Sync + 'static> BumpedEventBuilder<'w, T> {
+    #[must_use]
+    pub(crate) fn new(
+        build: impl for<'a> FnOnce(&'a bumpalo::Bump) -> &'a T + Send + Sync + 'static,
+        commands: Commands<'w, 'w>,
+    ) -> Self {
+        Self {
+            event: None,
+            commands,
+            targets: Vec::new(),
+            components: Vec::new(),
+            build: Some(Box::new(build)),
+        }
+    }
+
+    /// Adds `target` to the list of entities targeted by `self`
+    #[must_use]
+    pub fn entity(&mut self, target: Entity) -> &mut Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Sets the event id of the resulting event, used for dynamic events
+    /// # Safety
+    /// Caller must ensure that the component associated with `id` has the same layout as `Bumped<T>`
+    #[must_use]
+    pub unsafe fn event_id(&mut self, id: ComponentId) -> &mut Self {
+        self.event = Some(id);
+        self
+    }
+
+    /// Adds `component_id` to the list of components targeted by `self`
+    #[must_use]
+    pub fn component(&mut self, component_id: ComponentId) -> &mut Self {
+        self.components.push(component_id);
+        self
+    }
+
+    /// Add the event to the command queue of world
+    pub fn emit(&mut self) {
+        self.commands.add(EmitBumpedEvent::<T> {
+            event: self.event,
+            build: std::mem::take(&mut self.build).unwrap(),
+            entities: std::mem::take(&mut self.targets),
+            components: std::mem::take(&mut self.components),
+        });
+    }
+}
+
 impl<'w, 's> Commands<'w, 's> {
     /// Constructs an [`EventBuilder`] for an [`EcsEvent`].
     pub fn event<E: EcsEvent>(&mut self, event: E) -> EventBuilder<E> {
         EventBuilder::new(event, self.reborrow())
     }
 
+    /// Constructs a [`BumpedEventBuilder`] whose data is built by `build` directly
+    /// into the current broadcast's bump arena instead of being cloned in to satisfy
+    /// [`EcsEvent`]'s `'static` bound: observers see it through [`Bumped<T>`].
+    pub fn emit_with<T: ?Sized + Send + Sync + 'static>(
+        &mut self,
+        build: impl for<'a> FnOnce(&'a bumpalo::Bump) -> &'a T + Send + Sync + 'static,
+    ) -> BumpedEventBuilder<T> {
+        BumpedEventBuilder::new(build, self.reborrow())
+    }
+
     /// Construct an [`ObserverBuilder`]
     pub fn observer_builder<E: EcsEvent>(&mut self) -> ObserverBuilder<E> {
         ObserverBuilder::new(self.reborrow())
@@ -0,0 +1,847 @@
+//! Core observer/event types shared by [`builder`] and [`runner`].
+//!
+//! This module owns the actual dispatch machinery (matching observers against an
+//! emitted event, running them in order, and propagating targeted events along a
+//! relationship): [`builder`] only builds descriptors and [`runner`] only runs a
+//! single observer's system, so the code that ties a whole broadcast together lives
+//! here, alongside [`World::register_observer`]/[`World::unregister_observer`].
+
+mod builder;
+mod runner;
+
+pub use builder::{BumpedEventBuilder, EventBuilder, ObserverBuilder};
+pub use runner::{BoxedObserverSystem, ObserverComponent, ObserverRunner};
+
+use std::any::TypeId;
+use std::ops::{Deref, DerefMut};
+
+use bumpalo::Bump;
+
+use crate::{
+    bundle::Bundle,
+    component::{Component, ComponentId, ComponentInfo, SparseStorage},
+    entity::Entity,
+    ptr::PtrMut,
+    query::FilteredAccessSet,
+    system::{Command, Commands, Resource},
+    world::{DeferredWorld, World},
+};
+
+/// Marker trait for types that can be triggered as an ECS event through
+/// [`Commands::event`] and observed via [`ObserverBuilder`]/[`Commands::observer`].
+pub trait EcsEvent: Send + Sync + 'static {}
+
+/// Placeholder event type used by a fresh [`ObserverBuilder`] before `on_event`
+/// narrows it to a concrete event; never actually emitted.
+pub struct NoEvent;
+
+impl EcsEvent for NoEvent {}
+
+/// Sentinel [`ComponentId`] standing in for "no event type yet" on a fresh
+/// [`ObserverBuilder`]; never matched against a real emitted event.
+pub(crate) const NO_EVENT: ComponentId = ComponentId::new(usize::MAX);
+
+/// A relationship component an event can propagate along, e.g. `Parent` to bubble a
+/// targeted event up an entity hierarchy. Set per-observer via
+/// [`ObserverBuilder::traversal`].
+pub trait Traversal: Component {
+    /// Returns the next entity a propagating event should visit after this one, or
+    /// `None` to stop propagation here.
+    fn traverse(&self) -> Option<Entity>;
+}
+
+fn traverse_erased<T: Traversal>(world: &World, entity: Entity) -> Option<Entity> {
+    world.get::<T>(entity)?.traverse()
+}
+
+/// Describes the events, components, sources, and propagation policy an
+/// [`ObserverComponent`] is registered to observe.
+#[derive(Clone, Default)]
+pub(crate) struct ObserverDescriptor {
+    pub(crate) events: Vec<ComponentId>,
+    pub(crate) components: Vec<ComponentId>,
+    pub(crate) sources: Vec<Entity>,
+    /// The relationship component (set via [`ObserverBuilder::traversal`]) this
+    /// observer's event should propagate along once every observer matched at the
+    /// current hop has run. `None` means this observer doesn't drive propagation.
+    pub(crate) traversal: Option<(ComponentId, fn(&World, Entity) -> Option<Entity>)>,
+    /// Determines this observer's position among other observers matched at the same
+    /// hop: lower values run first. Defaults to `0`. Set via
+    /// [`ObserverBuilder::priority`].
+    pub(crate) priority: i32,
+}
+
+/// Identifies the observer entity currently running and the event hop it's running
+/// for. Handed to the [`ObserverRunner`] by mutable reference so propagation can read
+/// back [`ObserverTrigger::propagate`] after the observer's system runs.
+#[derive(Clone, Copy)]
+pub struct ObserverTrigger {
+    /// The entity holding the [`ObserverComponent`] currently being run.
+    pub observer: Entity,
+    /// The entity this event was originally targeted at; fixed for the whole
+    /// propagation, even as `current_target` advances hop to hop.
+    pub source: Entity,
+    /// The entity whose observers are running for the current hop. Starts out equal
+    /// to `source` and is advanced between hops as the event propagates.
+    pub current_target: Entity,
+    /// Whether the event should keep propagating past the current hop once every
+    /// observer matched here has run. Defaults to `true`; an observer can call
+    /// [`Observer::stop_propagation`] to flip it.
+    pub propagate: bool,
+}
+
+/// The handle an observer system receives as its `In` parameter: the triggering
+/// event's data plus the trigger metadata for the current hop.
+///
+/// Holds raw pointers rather than borrows: like [`ObserverRunner`], the surrounding
+/// dispatch code erases the real lifetime with `std::mem::transmute` so the type can
+/// satisfy `ObserverSystem::In = Observer<E, B>` (no lifetime parameter) while still
+/// pointing at data that's only valid for the current hop.
+pub struct Observer<E, B = ()> {
+    data: *mut E,
+    trigger: *mut ObserverTrigger,
+    _marker: std::marker::PhantomData<fn(B)>,
+}
+
+impl<E, B> Observer<E, B> {
+    pub(crate) fn new(data: &mut E, trigger: &mut ObserverTrigger) -> Self {
+        Self {
+            data,
+            trigger,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// The entity whose observers are running for the current propagation hop.
+    pub fn target(&self) -> Entity {
+        unsafe { &*self.trigger }.current_target
+    }
+
+    /// The entity this event was originally targeted at.
+    pub fn source(&self) -> Entity {
+        unsafe { &*self.trigger }.source
+    }
+
+    /// Stops the event from propagating past the current hop. Has no effect on
+    /// whether other observers matched at this same hop still run.
+    pub fn stop_propagation(&mut self) {
+        unsafe { &mut *self.trigger }.propagate = false;
+    }
+}
+
+impl<E, B> Deref for Observer<E, B> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        unsafe { &*self.data }
+    }
+}
+
+impl<E, B> DerefMut for Observer<E, B> {
+    fn deref_mut(&mut self) -> &mut E {
+        unsafe { &mut *self.data }
+    }
+}
+
+/// Per-world index of which observer entities are registered for which event,
+/// populated by [`World::register_observer`] and consulted by [`EmitEcsEvent`].
+#[derive(Default)]
+pub(crate) struct ObserverIndex {
+    by_event: std::collections::HashMap<ComponentId, Vec<Entity>>,
+    /// Set whenever the registered set of observers changes; cleared the next time
+    /// [`ensure_observer_access_checked`] actually re-validates access. Lets dispatch
+    /// skip the (quadratic) conflict check entirely on every broadcast between
+    /// registration changes, rather than paying its cost up front on every hop.
+    access_dirty: bool,
+}
+
+impl Resource for ObserverIndex {}
+
+impl World {
+    /// Adds `entity`'s [`ObserverComponent`] to this world's observer index.
+    pub(crate) fn register_observer(&mut self, entity: Entity) {
+        let Some(descriptor) = self
+            .get::<ObserverComponent>(entity)
+            .map(|observer| observer.descriptor.clone())
+        else {
+            return;
+        };
+        let mut index = self.get_resource_or_insert_with(ObserverIndex::default);
+        for event in &descriptor.events {
+            index.by_event.entry(*event).or_default().push(entity);
+        }
+        index.access_dirty = true;
+    }
+
+    /// Removes `entity` from this world's observer index.
+    pub(crate) fn unregister_observer(&mut self, entity: Entity) {
+        let Some(mut index) = self.get_resource_mut::<ObserverIndex>() else {
+            return;
+        };
+        for observers in index.by_event.values_mut() {
+            observers.retain(|&observer| observer != entity);
+        }
+        index.access_dirty = true;
+    }
+}
+
+/// Re-validates that no two currently-registered observers have conflicting component
+/// access, aggregating every conflicting pair into a single panic naming both
+/// entities. A no-op unless [`ObserverIndex::access_dirty`] is set, so registration
+/// changes pay for this once rather than every observer dispatch paying for it again.
+fn ensure_observer_access_checked(world: &mut World) {
+    let should_check = world
+        .get_resource::<ObserverIndex>()
+        .is_some_and(|index| index.access_dirty);
+    if !should_check {
+        return;
+    }
+
+    let entities: std::collections::BTreeSet<Entity> = world
+        .get_resource::<ObserverIndex>()
+        .map(|index| index.by_event.values().flatten().copied().collect())
+        .unwrap_or_default();
+
+    if let Some(mut index) = world.get_resource_mut::<ObserverIndex>() {
+        index.access_dirty = false;
+    }
+
+    let accesses: Vec<(Entity, FilteredAccessSet<ComponentId>)> = entities
+        .into_iter()
+        .filter_map(|entity| {
+            world
+                .get::<ObserverComponent>(entity)
+                .map(|observer| (entity, observer.component_access.clone()))
+        })
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for i in 0..accesses.len() {
+        for j in (i + 1)..accesses.len() {
+            let (entity_a, access_a) = &accesses[i];
+            let (entity_b, access_b) = &accesses[j];
+            if !access_a.is_compatible(access_b) {
+                conflicts.push((*entity_a, *entity_b));
+            }
+        }
+    }
+
+    assert!(
+        conflicts.is_empty(),
+        "Conflicting observer component access between: {}",
+        conflicts
+            .iter()
+            .map(|(a, b)| format!("({a:?}, {b:?})"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+}
+
+/// Matches observer entities registered for `event_id` against `target`/`components`,
+/// sorted by ascending [`ObserverDescriptor::priority`] with a stable tie-break on the
+/// observer's own [`Entity`] so equal-priority observers still run in a deterministic
+/// order.
+fn matching_observers(
+    world: &World,
+    event_id: ComponentId,
+    target: Entity,
+    components: &[ComponentId],
+) -> Vec<Entity> {
+    let Some(index) = world.get_resource::<ObserverIndex>() else {
+        return Vec::new();
+    };
+    let Some(candidates) = index.by_event.get(&event_id) else {
+        return Vec::new();
+    };
+
+    let mut matched: Vec<(i32, Entity)> = candidates
+        .iter()
+        .filter_map(|&entity| {
+            let observer = world.get::<ObserverComponent>(entity)?;
+            let sources_ok = observer.descriptor.sources.is_empty()
+                || observer.descriptor.sources.contains(&target);
+            let components_ok = observer.descriptor.components.is_empty()
+                || components
+                    .iter()
+                    .any(|c| observer.descriptor.components.contains(c));
+            (sources_ok && components_ok).then_some((observer.descriptor.priority, entity))
+        })
+        .collect();
+
+    matched.sort();
+    matched.into_iter().map(|(_, entity)| entity).collect()
+}
+
+/// Finds the next hop for a propagating event: the first traversal set by any
+/// observer matched at `target` that actually yields a next entity.
+fn resolve_next_target(world: &World, target: Entity, matched: &[Entity]) -> Option<Entity> {
+    matched.iter().find_map(|&entity| {
+        let (_, traverse) = world
+            .get::<ObserverComponent>(entity)?
+            .descriptor
+            .traversal?;
+        traverse(world, target)
+    })
+}
+
+/// Runs every observer matched at `target`, then (if nothing halted it) advances to
+/// the next hop via whichever matched observer set a traversal, repeating until
+/// propagation is halted or no traversal yields a further entity.
+fn dispatch_hop<E: EcsEvent>(
+    world: &mut World,
+    event_id: ComponentId,
+    data: &mut E,
+    source: Entity,
+    components: &[ComponentId],
+) {
+    ensure_observer_access_checked(world);
+
+    let mut current_target = source;
+    loop {
+        // Snapshot the matching set before running anything for this hop: component
+        // adds/removes a handler makes don't retarget the hop currently broadcasting.
+        let matched = matching_observers(world, event_id, current_target, components);
+
+        let mut propagate = true;
+        for observer_entity in &matched {
+            let Some(runner) = world
+                .get::<ObserverComponent>(*observer_entity)
+                .map(|observer| observer.runner)
+            else {
+                // Unregistered by an earlier observer in this same hop; the snapshot
+                // is stale for it, so just skip it.
+                continue;
+            };
+            let mut trigger = ObserverTrigger {
+                observer: *observer_entity,
+                source,
+                current_target,
+                propagate: true,
+            };
+            let ptr = PtrMut::from(&mut *data);
+            let deferred = DeferredWorld::from(&mut *world);
+            runner(deferred, &mut trigger, ptr);
+            propagate &= trigger.propagate;
+        }
+
+        if !propagate {
+            break;
+        }
+        match resolve_next_target(world, current_target, &matched) {
+            Some(next) => current_target = next,
+            None => break,
+        }
+    }
+}
+
+/// Thin [`bumpalo::Bump`] wrapper that asserts `Sync`, which [`Resource`] requires but
+/// `Bump` itself can't provide (its chunk cursor is a `Cell`).
+struct SyncBump(Bump);
+
+// SAFETY: the arena is only ever reached through `&mut World`/`&mut
+// ObserverDispatchState` in this module, so it's never actually touched
+// concurrently; `Resource` requiring `Sync` here is a formality this upholds.
+unsafe impl Sync for SyncBump {}
+
+impl Default for SyncBump {
+    fn default() -> Self {
+        Self(Bump::new())
+    }
+}
+
+impl Deref for SyncBump {
+    type Target = Bump;
+
+    fn deref(&self) -> &Bump {
+        &self.0
+    }
+}
+
+impl DerefMut for SyncBump {
+    fn deref_mut(&mut self) -> &mut Bump {
+        &mut self.0
+    }
+}
+
+/// An event payload allocated straight into a broadcast's bump arena, with the
+/// borrow's real lifetime erased much like [`Observer`] already erases the current
+/// hop's: the arena backing a `Bumped<T>` ([`ObserverDispatchState::arena`]) isn't
+/// reset until the whole broadcast queue finishes draining (see [`DispatchGuard`]),
+/// which outlives every observer a `Bumped<T>` is ever handed to — so treating the
+/// pointer as `'static` to satisfy [`EcsEvent`] is sound, even though `T` itself
+/// usually isn't. Built via [`Commands::emit_with`].
+pub struct Bumped<T: ?Sized> {
+    data: *const T,
+}
+
+impl<T: ?Sized> Bumped<T> {
+    /// # Safety
+    /// `data` must stay valid until the broadcast's arena is next reset.
+    unsafe fn new(data: &T) -> Self {
+        Self { data }
+    }
+}
+
+impl<T: ?Sized> Deref for Bumped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.data }
+    }
+}
+
+// SAFETY: see the struct doc comment: a `Bumped<T>` only ever points into a world's
+// own dispatch arena, which is itself never touched concurrently.
+unsafe impl<T: ?Sized + Sync> Send for Bumped<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Bumped<T> {}
+
+impl<T: ?Sized + Sync> EcsEvent for Bumped<T> {}
+
+/// Per-world queue of not-yet-dispatched broadcasts, used to make nested/recursive
+/// `emit`s (e.g. an observer emitting another event, or a structural change queued
+/// mid-broadcast) well-defined: the broadcast already in progress always finishes
+/// before the next one starts, instead of interleaving hop to hop.
+#[derive(Default)]
+pub(crate) struct ObserverDispatchState {
+    pending: std::collections::VecDeque<Box<dyn FnOnce(&mut World) + Send + Sync>>,
+    /// `true` for the entire duration of the outermost `EmitEcsEvent::apply`, including
+    /// every broadcast it drains from `pending`. A nested `apply` sees this set and
+    /// queues onto `pending` instead of dispatching inline.
+    dispatching: bool,
+    /// Backs [`Bumped`] event payloads built via [`Commands::emit_with`]. Reset once
+    /// the whole broadcast queue fully drains, since nothing is meant to hold onto a
+    /// `Bumped` handle past the hop it was delivered to.
+    arena: SyncBump,
+}
+
+impl Resource for ObserverDispatchState {}
+
+/// Resets [`ObserverDispatchState::dispatching`] and reclaims the arena when the
+/// outermost broadcast finishes, whether it returns normally or unwinds. Without the
+/// `dispatching` reset, a panicking observer would leave it stuck at `true` forever,
+/// silently turning every future `emit` on this world into a no-op that just queues
+/// and never drains.
+struct DispatchGuard(*mut World);
+
+impl Drop for DispatchGuard {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` was built from the same `&mut World` this guard's lifetime
+        // is scoped to, and nothing else touches it for the guard's duration.
+        let world = unsafe { &mut *self.0 };
+        if let Some(mut state) = world.get_resource_mut::<ObserverDispatchState>() {
+            state.dispatching = false;
+            state.arena.reset();
+        }
+    }
+}
+
+/// Dispatches `run` now if no broadcast is already in progress on `world`, otherwise
+/// queues it onto [`ObserverDispatchState::pending`] to run once the in-progress one
+/// (and everything it queues in turn) fully drains. Shared by [`EmitEcsEvent`] and
+/// [`EmitBumpedEvent`] so both kinds of broadcast serialize against each other.
+fn emit_or_queue(world: &mut World, run: impl FnOnce(&mut World) + Send + Sync + 'static) {
+    let mut state = world.get_resource_or_insert_with(ObserverDispatchState::default);
+    if state.dispatching {
+        state.pending.push_back(Box::new(run));
+        return;
+    }
+    state.dispatching = true;
+    drop(state);
+
+    let guard = DispatchGuard(world as *mut World);
+    run(world);
+    loop {
+        let Some(mut state) = world.get_resource_mut::<ObserverDispatchState>() else {
+            break;
+        };
+        let Some(next) = state.pending.pop_front() else {
+            break;
+        };
+        next(world);
+    }
+    drop(guard);
+}
+
+/// [`Command`] queued by [`EventBuilder::emit`]: dispatches `data` to every matching
+/// observer and propagates it hop to hop until halted.
+pub(crate) struct EmitEcsEvent<E> {
+    pub(crate) event: Option<ComponentId>,
+    pub(crate) data: E,
+    pub(crate) entities: Vec<Entity>,
+    pub(crate) components: Vec<ComponentId>,
+}
+
+impl<E: EcsEvent> EmitEcsEvent<E> {
+    fn dispatch(self, world: &mut World) {
+        let event_id = self.event.unwrap_or_else(|| {
+            world
+                .components()
+                .get_id(TypeId::of::<E>())
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Cannot emit unregistered event: {}",
+                        std::any::type_name::<E>()
+                    )
+                })
+        });
+        world.last_event_id = world.last_event_id.wrapping_add(1);
+        let mut data = self.data;
+        if self.entities.is_empty() {
+            dispatch_hop(world, event_id, &mut data, Entity::PLACEHOLDER, &self.components);
+        }
+        for &source in &self.entities {
+            dispatch_hop(world, event_id, &mut data, source, &self.components);
+        }
+    }
+}
+
+impl<E: EcsEvent> Command for EmitEcsEvent<E> {
+    fn apply(self, world: &mut World) {
+        emit_or_queue(world, move |world| self.dispatch(world));
+    }
+}
+
+/// [`Command`] queued by [`BumpedEventBuilder::emit`]: builds the event's data
+/// straight into the broadcast's bump arena (see [`ObserverDispatchState::arena`])
+/// right before dispatching it, instead of needing an already-owned, independent
+/// value the way [`EmitEcsEvent`] does.
+pub(crate) struct EmitBumpedEvent<T: ?Sized> {
+    pub(crate) event: Option<ComponentId>,
+    pub(crate) build: Box<dyn for<'a> FnOnce(&'a Bump) -> &'a T + Send + Sync>,
+    pub(crate) entities: Vec<Entity>,
+    pub(crate) components: Vec<ComponentId>,
+}
+
+impl<T: ?Sized + Send + Sync + 'static> EmitBumpedEvent<T> {
+    fn dispatch(self, world: &mut World) {
+        let event_id = self.event.unwrap_or_else(|| {
+            world
+                .components()
+                .get_id(TypeId::of::<Bumped<T>>())
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Cannot emit unregistered event: {}",
+                        std::any::type_name::<Bumped<T>>()
+                    )
+                })
+        });
+        world.last_event_id = world.last_event_id.wrapping_add(1);
+
+        let mut state = world.get_resource_or_insert_with(ObserverDispatchState::default);
+        let built = (self.build)(&state.arena);
+        // SAFETY: `built` is only ever reclaimed when `state.arena` itself is reset,
+        // which doesn't happen until the whole broadcast queue this dispatch is part
+        // of has fully drained (see `DispatchGuard`).
+        let mut data = unsafe { Bumped::new(built) };
+        drop(state);
+
+        if self.entities.is_empty() {
+            dispatch_hop(world, event_id, &mut data, Entity::PLACEHOLDER, &self.components);
+        }
+        for &source in &self.entities {
+            dispatch_hop(world, event_id, &mut data, source, &self.components);
+        }
+    }
+}
+
+impl<T: ?Sized + Send + Sync + 'static> Command for EmitBumpedEvent<T> {
+    fn apply(self, world: &mut World) {
+        emit_or_queue(world, move |world| self.dispatch(world));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Component)]
+    struct Parent(Entity);
+
+    impl Traversal for Parent {
+        fn traverse(&self) -> Option<Entity> {
+            Some(self.0)
+        }
+    }
+
+    struct Bubble;
+    impl EcsEvent for Bubble {}
+
+    #[test]
+    fn propagation_bubbles_up_the_parent_chain() {
+        let mut world = World::new();
+        world.init_component::<Parent>();
+        world.init_component::<Bubble>();
+
+        let grandparent = world.spawn_empty().id();
+        let parent = world.spawn(Parent(grandparent)).id();
+        let child = world.spawn(Parent(parent)).id();
+
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let visited_handle = visited.clone();
+        world
+            .commands()
+            .observer_builder::<Bubble>()
+            .traversal::<Parent>()
+            .run(move |observer: Observer<Bubble>| {
+                visited_handle.lock().unwrap().push(observer.target());
+            });
+        world.flush();
+
+        world.commands().event(Bubble).entity(child).emit();
+        world.flush();
+
+        assert_eq!(*visited.lock().unwrap(), vec![child, parent, grandparent]);
+    }
+
+    #[test]
+    fn stop_propagation_keeps_the_bubble_from_reaching_the_parent() {
+        let mut world = World::new();
+        world.init_component::<Parent>();
+        world.init_component::<Bubble>();
+
+        let parent = world.spawn_empty().id();
+        let child = world.spawn(Parent(parent)).id();
+
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let visited_handle = visited.clone();
+        world
+            .commands()
+            .observer_builder::<Bubble>()
+            .traversal::<Parent>()
+            .run(move |mut observer: Observer<Bubble>| {
+                visited_handle.lock().unwrap().push(observer.target());
+                observer.stop_propagation();
+            });
+        world.flush();
+
+        world.commands().event(Bubble).entity(child).emit();
+        world.flush();
+
+        assert_eq!(*visited.lock().unwrap(), vec![child]);
+    }
+
+    #[test]
+    fn untargeted_emit_still_reaches_observers_with_no_source_filter() {
+        let mut world = World::new();
+        world.init_component::<Bubble>();
+
+        let ran = Arc::new(Mutex::new(false));
+        let ran_handle = ran.clone();
+        world
+            .commands()
+            .observer_builder::<Bubble>()
+            .run(move |_: Observer<Bubble>| *ran_handle.lock().unwrap() = true);
+        world.flush();
+
+        // No `.entity(...)` call: this event has no target, so `source` (and every
+        // hop's `current_target`) stays `Entity::PLACEHOLDER` throughout dispatch.
+        world.commands().event(Bubble).emit();
+        world.flush();
+
+        assert!(*ran.lock().unwrap());
+    }
+
+    #[test]
+    fn observers_run_in_priority_order() {
+        let mut world = World::new();
+        world.init_component::<Bubble>();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let order_handle = order.clone();
+        world
+            .commands()
+            .observer_builder::<Bubble>()
+            .priority(10)
+            .run(move |_: Observer<Bubble>| order_handle.lock().unwrap().push("second"));
+
+        let order_handle = order.clone();
+        world
+            .commands()
+            .observer_builder::<Bubble>()
+            .priority(-10)
+            .run(move |_: Observer<Bubble>| order_handle.lock().unwrap().push("first"));
+
+        world.flush();
+
+        world.commands().event(Bubble).emit();
+        world.flush();
+
+        assert_eq!(*order.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn observers_with_equal_priority_break_ties_on_entity() {
+        let mut world = World::new();
+        world.init_component::<Bubble>();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let order_handle = order.clone();
+        let first = world
+            .commands()
+            .observer_builder::<Bubble>()
+            .run(move |_: Observer<Bubble>| order_handle.lock().unwrap().push("first"));
+
+        let order_handle = order.clone();
+        let second = world
+            .commands()
+            .observer_builder::<Bubble>()
+            .run(move |_: Observer<Bubble>| order_handle.lock().unwrap().push("second"));
+
+        world.flush();
+        assert!(first < second);
+
+        world.commands().event(Bubble).emit();
+        world.flush();
+
+        assert_eq!(*order.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn a_nested_emit_drains_after_the_outer_broadcast_finishes() {
+        struct Outer;
+        impl EcsEvent for Outer {}
+
+        struct Inner;
+        impl EcsEvent for Inner {}
+
+        let mut world = World::new();
+        world.init_component::<Outer>();
+        world.init_component::<Inner>();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let order_handle = order.clone();
+        world.commands().observer_builder::<Outer>().run(
+            move |_: Observer<Outer>, mut commands: Commands| {
+                order_handle.lock().unwrap().push("outer starts");
+                commands.event(Inner).emit();
+                order_handle.lock().unwrap().push("outer ends");
+            },
+        );
+
+        let order_handle = order.clone();
+        world
+            .commands()
+            .observer_builder::<Inner>()
+            .run(move |_: Observer<Inner>| order_handle.lock().unwrap().push("inner runs"));
+
+        world.flush();
+
+        world.commands().event(Outer).emit();
+        world.flush();
+
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["outer starts", "outer ends", "inner runs"]
+        );
+    }
+
+    #[derive(Component)]
+    struct Foo(i32);
+
+    #[derive(Component)]
+    struct Bar(i32);
+
+    #[test]
+    #[should_panic(expected = "Conflicting observer component access")]
+    fn conflicting_observer_access_panics() {
+        let mut world = World::new();
+        world.init_component::<Bubble>();
+
+        world.commands().observer_builder::<Bubble>().run(
+            |_: Observer<Bubble>, mut query: Query<&mut Foo>| {
+                for _ in query.iter_mut() {}
+            },
+        );
+        world.commands().observer_builder::<Bubble>().run(
+            |_: Observer<Bubble>, mut query: Query<&mut Foo>| {
+                for _ in query.iter_mut() {}
+            },
+        );
+        world.flush();
+
+        world.commands().event(Bubble).emit();
+        world.flush();
+    }
+
+    #[test]
+    fn observers_with_disjoint_component_access_do_not_panic() {
+        let mut world = World::new();
+        world.init_component::<Bubble>();
+
+        world.commands().observer_builder::<Bubble>().run(
+            |_: Observer<Bubble>, mut query: Query<&mut Foo>| {
+                for _ in query.iter_mut() {}
+            },
+        );
+        world.commands().observer_builder::<Bubble>().run(
+            |_: Observer<Bubble>, mut query: Query<&mut Bar>| {
+                for _ in query.iter_mut() {}
+            },
+        );
+        world.flush();
+
+        world.commands().event(Bubble).emit();
+        world.flush();
+    }
+
+    #[test]
+    fn emit_with_builds_the_payload_from_the_broadcast_arena() {
+        let mut world = World::new();
+        world.init_component::<Bumped<str>>();
+
+        let heard = Arc::new(Mutex::new(String::new()));
+        let heard_handle = heard.clone();
+        world.commands().observer_builder::<Bumped<str>>().run(
+            move |observer: Observer<Bumped<str>>| {
+                *heard_handle.lock().unwrap() = observer.to_string();
+            },
+        );
+        world.flush();
+
+        world
+            .commands()
+            .emit_with::<str>(|arena| arena.alloc_str("hello from the arena"))
+            .emit();
+        world.flush();
+
+        assert_eq!(*heard.lock().unwrap(), "hello from the arena");
+    }
+
+    #[test]
+    fn the_arena_is_reused_across_broadcasts_once_the_queue_drains() {
+        let mut world = World::new();
+        world.init_component::<Bumped<str>>();
+
+        let addresses = Arc::new(Mutex::new(Vec::new()));
+        let addresses_handle = addresses.clone();
+        world.commands().observer_builder::<Bumped<str>>().run(
+            move |observer: Observer<Bumped<str>>| {
+                addresses_handle.lock().unwrap().push(observer.as_ptr() as usize);
+            },
+        );
+        world.flush();
+
+        // Both payloads have the same length: the arena bumps downward, so they only
+        // land at the same address if it was reset between the two broadcasts.
+        world
+            .commands()
+            .emit_with::<str>(|arena| arena.alloc_str("ping"))
+            .emit();
+        world.flush();
+
+        world
+            .commands()
+            .emit_with::<str>(|arena| arena.alloc_str("pong"))
+            .emit();
+        world.flush();
+
+        let addresses = addresses.lock().unwrap();
+        assert_eq!(addresses[0], addresses[1]);
+    }
+}
@@ -1,10 +1,15 @@
+use crate::query::FilteredAccessSet;
 use crate::system::{IntoObserverSystem, ObserverSystem};
 
 use super::*;
 
 /// Type for function that is run when an observer is triggered
 /// Typically refers to the default runner defined in [`ObserverComponent::from`]
-pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut);
+///
+/// The runner is handed `trigger` by mutable reference so that a system halting
+/// propagation via its [`Observer`] handle writes straight through to the trigger
+/// the caller driving the hop-to-hop loop is holding (see [`EmitEcsEvent`]).
+pub type ObserverRunner = fn(DeferredWorld, &mut ObserverTrigger, PtrMut);
 
 pub type BoxedObserverSystem<E = (), B = ()> = Box<dyn ObserverSystem<E, B>>;
 
@@ -13,6 +18,15 @@ pub(crate) struct ObserverComponent {
     pub(crate) runner: ObserverRunner,
     pub(crate) system: Option<BoxedObserverSystem>,
     pub(crate) last_event_id: u32,
+    /// The [`ObserverTrigger::current_target`] this observer last ran for, alongside
+    /// `last_event_id`. Propagating an event revisits the same `last_event_id` at a new
+    /// target, so the pair is what makes dedup scoped to a single hop instead of to the
+    /// whole bubbling traversal.
+    pub(crate) last_hop_target: Entity,
+    /// This observer's component access, captured once at `system.initialize(world)`
+    /// time and consulted by [`ensure_observer_access_checked`] to catch two observers
+    /// with conflicting access before either one ever runs, rather than racing them.
+    pub(crate) component_access: FilteredAccessSet<ComponentId>,
 }
 
 impl Component for ObserverComponent {
@@ -44,13 +58,11 @@ impl ObserverComponent {
             "Cannot run exclusive systems in Observers"
         );
         system.initialize(world);
+        let component_access = system.component_access_set().clone();
         let system: BoxedObserverSystem<E, B> = Box::new(system);
         Self {
             descriptor,
             runner: |mut world, trigger, ptr| {
-                if trigger.source == Entity::PLACEHOLDER {
-                    return;
-                }
                 let world = world.as_unsafe_world_cell();
                 let observer_cell =
                     unsafe { world.get_entity(trigger.observer).debug_checked_unwrap() };
@@ -59,12 +71,21 @@ impl ObserverComponent {
                         .get_mut::<ObserverComponent>()
                         .debug_checked_unwrap()
                 };
+                // Dedup is scoped to this hop: a run for the same event at the same
+                // target as this observer's last run is skipped, but a new hop (a new
+                // `current_target` reached via propagation) is allowed to run it again.
                 let last_event = unsafe { world.world() }.last_event_id;
-                if state.last_event_id == last_event {
+                if state.last_event_id == last_event
+                    && state.last_hop_target == trigger.current_target
+                {
                     return;
                 }
                 state.last_event_id = last_event;
+                state.last_hop_target = trigger.current_target;
 
+                // `trigger` is handed to `Observer` by mutable reference, so a system
+                // calling `Observer::stop_propagation` writes straight through to the
+                // trigger the caller driving propagation is holding.
                 let observer: Observer<E, B> = Observer::new(unsafe { ptr.deref_mut() }, trigger);
                 let mut system: Box<dyn ObserverSystem<E, B>> = unsafe {
                     let system = state.system.take().debug_checked_unwrap();
@@ -79,6 +100,8 @@ impl ObserverComponent {
                 }
             },
             last_event_id: 0,
+            last_hop_target: Entity::PLACEHOLDER,
+            component_access,
             // SAFETY: Same layout
             system: Some(unsafe { std::mem::transmute(system) }),
         }
@@ -89,6 +112,11 @@ impl ObserverComponent {
             descriptor,
             runner,
             last_event_id: 0,
+            last_hop_target: Entity::PLACEHOLDER,
+            // A raw runner has no `System` to ask for its access, so it's recorded as
+            // accessing nothing; it never conflicts with another observer and it's on
+            // the caller to make sure that's actually true.
+            component_access: FilteredAccessSet::default(),
             system: None,
         }
     }